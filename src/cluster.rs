@@ -0,0 +1,77 @@
+/// Number of hash slots a cluster keyspace is divided into.
+pub const SLOT_COUNT: u16 = 16384;
+
+/// Compute the cluster hash slot for a key.
+///
+/// If the key contains a non-empty `{tag}`, only the tag is hashed, so keys
+/// like `{user:1}:name` and `{user:1}:age` land in the same slot.
+pub fn key_slot(key: &[u8]) -> u16 {
+    crc16(hash_tag(key)) % SLOT_COUNT
+}
+
+/// Return the part of the key that should be hashed: the bytes between the
+/// first `{` and the next `}` when non-empty, otherwise the whole key.
+fn hash_tag(key: &[u8]) -> &[u8] {
+    let Some(start) = key.iter().position(|&b| b == b'{') else {
+        return key;
+    };
+    match key[start + 1..].iter().position(|&b| b == b'}') {
+        Some(len) if len > 0 => &key[start + 1..start + 1 + len],
+        _ => key,
+    }
+}
+
+// CRC16-CCITT (XMODEM): polynomial 0x1021, initial value 0
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &b| {
+        (0..8).fold(crc ^ ((b as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_slot_should_match_redis() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+        assert_eq!(key_slot(b"123456789"), 12739);
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(key_slot(b"bar"), 5061);
+        assert_eq!(key_slot(b"{user1000}.following"), 3443);
+        assert_eq!(key_slot(b"{user1000}.followers"), 3443);
+        assert_eq!(key_slot(b""), 0);
+    }
+
+    #[test]
+    fn keys_with_same_tag_should_share_slot() {
+        assert_eq!(
+            key_slot(b"{user1000}.following"),
+            key_slot(b"{user1000}.followers")
+        );
+    }
+
+    #[test]
+    fn empty_tag_should_hash_whole_key() {
+        assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_COUNT);
+        assert_eq!(key_slot(b"foo{}{bar}"), 8363);
+    }
+
+    #[test]
+    fn nested_brace_should_hash_up_to_first_close() {
+        assert_eq!(hash_tag(b"foo{{bar}}zap"), b"{bar");
+        assert_eq!(key_slot(b"foo{{bar}}zap"), crc16(b"{bar") % SLOT_COUNT);
+    }
+
+    #[test]
+    fn unclosed_brace_should_hash_whole_key() {
+        assert_eq!(hash_tag(b"foo{bar"), b"foo{bar");
+        assert_eq!(key_slot(b"foo{bar"), crc16(b"foo{bar") % SLOT_COUNT);
+    }
+}
@@ -0,0 +1,4 @@
+//! Library target for simple-redis. `cluster` is the only public module so
+//! far; it exposes the key-slot mapping for embedders and proxies.
+
+pub mod cluster;